@@ -0,0 +1,245 @@
+use crate::error::Error;
+use std::process::Command;
+
+/// Record/unit separators used in the `git log` format so multi-line commit
+/// bodies can be split back apart unambiguously.
+const RECORD_SEP: char = '\x1e';
+const UNIT_SEP: char = '\x1f';
+
+/// A single conventional-commit entry parsed out of the git history.
+#[derive(Debug)]
+struct Entry {
+    ty: String,
+    scope: Option<String>,
+    description: String,
+    short_hash: String,
+    breaking: bool,
+}
+
+fn git(args: &[&str]) -> Result<String, Error> {
+    let out = Command::new("git").args(args).output()?;
+    if !out.status.success() {
+        return Err(Error::Command(
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// The most recent version tag, used as the default lower bound of the range.
+fn last_tag() -> Option<String> {
+    git(&["describe", "--tags", "--abbrev=0"])
+        .ok()
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+}
+
+/// Parse a conventional-commit subject of the form `type(scope)!: description`.
+/// Returns `None` for subjects that don't match the format.
+fn parse_subject(subject: &str) -> Option<(String, Option<String>, bool, String)> {
+    let colon = subject.find(':')?;
+    let (head, rest) = subject.split_at(colon);
+    let description = rest[1..].trim().to_owned();
+    if description.is_empty() {
+        return None;
+    }
+
+    let mut breaking = false;
+    let head = head.strip_suffix('!').map_or(head, |h| {
+        breaking = true;
+        h
+    });
+
+    let (ty, scope) = match head.split_once('(') {
+        Some((ty, scope)) => {
+            let scope = scope.strip_suffix(')')?;
+            (ty, Some(scope.to_owned()))
+        }
+        None => (head, None),
+    };
+
+    // A type is a single alphabetic word; it is normalized to lowercase.
+    if ty.is_empty() || !ty.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some((ty.to_ascii_lowercase(), scope, breaking, description))
+}
+
+/// Whether a commit body carries a `BREAKING CHANGE:` footer, which promotes an
+/// entry to a breaking change regardless of its type.
+fn body_is_breaking(body: &str) -> bool {
+    body.contains("BREAKING CHANGE:")
+}
+
+fn collect(from: Option<&str>, to: &str) -> Result<Vec<Entry>, Error> {
+    let range = match from {
+        Some(from) => format!("{from}..{to}"),
+        None => to.to_owned(),
+    };
+    let format = format!("--format=%h{UNIT_SEP}%s{UNIT_SEP}%b{RECORD_SEP}");
+    let log = git(&["log", &range, &format])?;
+
+    let mut entries = Vec::new();
+    for record in log.split(RECORD_SEP) {
+        let record = record.trim_matches(['\n', '\r']);
+        if record.is_empty() {
+            continue;
+        }
+        let mut fields = record.splitn(3, UNIT_SEP);
+        let short_hash = fields.next().unwrap_or("").trim().to_owned();
+        let subject = fields.next().unwrap_or("");
+        let body = fields.next().unwrap_or("");
+
+        let Some((ty, scope, mut breaking, description)) = parse_subject(subject) else {
+            continue;
+        };
+        breaking = breaking || body_is_breaking(body);
+        entries.push(Entry {
+            ty,
+            scope,
+            description,
+            short_hash,
+            breaking,
+        });
+    }
+    Ok(entries)
+}
+
+fn render_line(entry: &Entry) -> String {
+    match &entry.scope {
+        Some(scope) => format!(
+            "- **{scope}:** {} ({})",
+            entry.description, entry.short_hash
+        ),
+        None => format!("- {} ({})", entry.description, entry.short_hash),
+    }
+}
+
+/// The type -> heading mapping, in the order sections should appear. Commit
+/// types outside this set are skipped, mirroring conventional-changelog.
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactors"),
+    ("docs", "Documentation"),
+];
+
+/// Render the changelog block for `version`, dated `date` (`YYYY-MM-DD`).
+fn render(version: &str, date: &str, entries: &[Entry]) -> String {
+    let mut out = format!("## {version} - {date}\n");
+
+    let breaking: Vec<&Entry> = entries.iter().filter(|e| e.breaking).collect();
+    if !breaking.is_empty() {
+        out.push_str("\n### BREAKING CHANGES\n\n");
+        for entry in breaking {
+            out.push_str(&render_line(entry));
+            out.push('\n');
+        }
+    }
+
+    for (ty, heading) in SECTIONS {
+        let lines: Vec<&Entry> = entries.iter().filter(|e| e.ty == *ty).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n### {heading}\n\n"));
+        for entry in lines {
+            out.push_str(&render_line(entry));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Prepend `block` to the changelog at `path`, creating it if absent.
+fn prepend(path: &str, block: &str) -> Result<(), Error> {
+    let existing = std::fs::read_to_string(path).unwrap_or_default();
+    let contents = if existing.trim().is_empty() {
+        format!("# Changelog\n\n{block}")
+    } else {
+        format!("{block}\n{existing}")
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Generate the changelog section for `version` over the given commit range and
+/// prepend it to `path`. `from`/`to` default to the last version tag and `HEAD`.
+pub fn generate(
+    version: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    path: &str,
+) -> Result<(), Error> {
+    let to = to.unwrap_or("HEAD");
+    let from = from.map(|s| s.to_owned()).or_else(last_tag);
+    let date = git(&["log", "-1", "--format=%ad", "--date=short", to])?
+        .trim()
+        .to_owned();
+    let entries = collect(from.as_deref(), to)?;
+    let block = render(version, &date, &entries);
+    prepend(path, &block)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_type_scope_and_description() {
+        let (ty, scope, breaking, desc) = parse_subject("feat(core): add parser").unwrap();
+        assert_eq!(ty, "feat");
+        assert_eq!(scope.as_deref(), Some("core"));
+        assert!(!breaking);
+        assert_eq!(desc, "add parser");
+    }
+
+    #[test]
+    fn parses_without_scope() {
+        let (ty, scope, breaking, desc) = parse_subject("fix: handle empty input").unwrap();
+        assert_eq!(ty, "fix");
+        assert_eq!(scope, None);
+        assert!(!breaking);
+        assert_eq!(desc, "handle empty input");
+    }
+
+    #[test]
+    fn detects_breaking_bang() {
+        let (ty, scope, breaking, _) = parse_subject("feat(api)!: drop v1 route").unwrap();
+        assert_eq!(ty, "feat");
+        assert_eq!(scope.as_deref(), Some("api"));
+        assert!(breaking);
+    }
+
+    #[test]
+    fn breaking_bang_without_scope() {
+        let (_, _, breaking, _) = parse_subject("refactor!: rework backend").unwrap();
+        assert!(breaking);
+    }
+
+    #[test]
+    fn rejects_non_conventional_subjects() {
+        assert!(parse_subject("just a normal commit").is_none());
+        assert!(parse_subject("feat:").is_none());
+        assert!(parse_subject("Feat1(x): nope").is_none());
+        assert!(parse_subject("feat(core: missing paren").is_none());
+    }
+
+    #[test]
+    fn breaking_change_footer_detected_in_body() {
+        assert!(body_is_breaking("some context\n\nBREAKING CHANGE: the API changed"));
+        assert!(!body_is_breaking("just a normal body"));
+    }
+
+    #[test]
+    fn footer_promotes_non_bang_entry_to_breaking() {
+        // A plain `feat:` subject is not breaking on its own, but a footer flips
+        // it — the same combination `collect` applies per commit.
+        let (_, _, subject_breaking, _) = parse_subject("feat: add thing").unwrap();
+        let breaking = subject_breaking || body_is_breaking("BREAKING CHANGE: removed old flag");
+        assert!(!subject_breaking);
+        assert!(breaking);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The bump level applied by `update` when no explicit flag is given.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    Major,
+    Minor,
+    #[default]
+    Patch,
+}
+
+/// The parsed `.vers` configuration.
+///
+/// `.vers` is a TOML document. A single-version file carries a top-level
+/// `version`; a monorepo file omits it and declares `[[package]]` entries
+/// instead. Everything else falls back to the defaults below.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Config {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Active pre-release suffix strategy, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+
+    /// Bump level used when `update` is run without `--major/--minor/--patch`.
+    #[serde(default)]
+    pub bump: BumpLevel,
+
+    /// Template for annotated tag names; `{version}` is substituted.
+    #[serde(default = "default_tag_template")]
+    pub tag_template: String,
+
+    /// Remote to push to.
+    #[serde(default = "default_remote")]
+    pub remote: String,
+
+    /// Branch to push.
+    #[serde(default = "default_branch")]
+    pub branch: String,
+
+    /// Packages managed in this repository. Empty means single-version mode.
+    #[serde(default, rename = "package", skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<Package>,
+}
+
+/// A single versioned package in a monorepo.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Package {
+    /// Identifier used to select the package on the command line.
+    pub name: String,
+
+    /// Root path the package owns, relative to the repository root.
+    pub path: String,
+
+    pub version: String,
+
+    /// Active pre-release suffix strategy for this package, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+
+    /// Prefix prepended to the version to form the tag name, e.g. `core-v`.
+    #[serde(default)]
+    pub tag_prefix: String,
+}
+
+impl Package {
+    /// The tag name for this package at its current version, e.g. `core-v1.2.0`.
+    pub fn tag_name(&self) -> String {
+        format!("{}{}", self.tag_prefix, self.version)
+    }
+}
+
+fn default_tag_template() -> String {
+    "v{version}".to_owned()
+}
+
+fn default_remote() -> String {
+    "origin".to_owned()
+}
+
+fn default_branch() -> String {
+    "main".to_owned()
+}
+
+impl Config {
+    /// The top-level version for single-version mode, trimmed. Returns an error
+    /// for a package-only `.vers`, which has no top-level `version`.
+    pub fn require_version(&self) -> Result<&str, Error> {
+        self.version.as_deref().map(str::trim).ok_or_else(|| {
+            Error::Command(
+                "`.vers` has no top-level `version`; use `--package` in monorepo mode".to_owned(),
+            )
+        })
+    }
+
+    /// Render the configured tag name for `version`.
+    pub fn tag_name(&self, version: &str) -> String {
+        self.tag_template.replace("{version}", version)
+    }
+
+    /// Walk up from the current directory looking for a `.vers` file and parse
+    /// it, so the tool works from anywhere inside a project.
+    pub fn discover() -> Result<(Config, PathBuf), Error> {
+        let cwd = std::env::current_dir()?;
+        let path = find_upwards(&cwd).ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no .vers file found in this or any parent directory",
+            ))
+        })?;
+        let config = Config::load(&path)?;
+        Ok((config, path))
+    }
+
+    /// Parse a `.vers` file at an explicit path.
+    pub fn load(path: &Path) -> Result<Config, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Serialize back to a `.vers` file.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let contents = toml::to_string(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+fn find_upwards(start: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start);
+    while let Some(d) = dir {
+        let candidate = d.join(".vers");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A unique, pre-created scratch directory for a test that touches the disk.
+    fn scratch(tag: &str) -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("vers-test-{}-{tag}-{n}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_single_field_file_with_defaults() {
+        let conf: Config = toml::from_str("version = \"1.2.3\"").unwrap();
+        assert_eq!(conf.version.as_deref(), Some("1.2.3"));
+        assert_eq!(conf.bump, BumpLevel::Patch);
+        assert_eq!(conf.tag_template, "v{version}");
+        assert_eq!(conf.remote, "origin");
+        assert_eq!(conf.branch, "main");
+        assert!(conf.packages.is_empty());
+    }
+
+    #[test]
+    fn tag_name_applies_template() {
+        let conf: Config = toml::from_str("version = \"1.2.3\"").unwrap();
+        assert_eq!(conf.tag_name("1.2.3"), "v1.2.3");
+    }
+
+    #[test]
+    fn round_trips_through_save_and_load() {
+        let dir = scratch("roundtrip");
+        let path = dir.join(".vers");
+        let conf: Config =
+            toml::from_str("version = \"0.4.1\"\nbump = \"minor\"\nremote = \"upstream\"").unwrap();
+        conf.save(&path).unwrap();
+        let reloaded = Config::load(&path).unwrap();
+        assert_eq!(reloaded.version.as_deref(), Some("0.4.1"));
+        assert_eq!(reloaded.bump, BumpLevel::Minor);
+        assert_eq!(reloaded.remote, "upstream");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn package_only_file_parses_and_require_version_errors() {
+        let conf: Config = toml::from_str(
+            "[[package]]\nname = \"core\"\npath = \"crates/core\"\nversion = \"1.0.0\"",
+        )
+        .unwrap();
+        assert!(conf.version.is_none());
+        assert_eq!(conf.packages.len(), 1);
+        assert!(matches!(conf.require_version(), Err(Error::Command(_))));
+    }
+
+    #[test]
+    fn find_upwards_resolves_from_nested_directory() {
+        let base = scratch("discover");
+        let vers = base.join(".vers");
+        std::fs::write(&vers, "version = \"1.0.0\"").unwrap();
+        let nested = base.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_upwards(&nested), Some(vers));
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn find_upwards_returns_none_without_a_vers_file() {
+        let dir = scratch("empty");
+        assert_eq!(find_upwards(&dir), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
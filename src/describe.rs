@@ -0,0 +1,120 @@
+use crate::config::Config;
+use crate::error::Error;
+use crate::version::Version;
+use std::process::Command;
+
+/// Suffix strategy name selecting git-describe-derived versions in `.vers`.
+pub const STRATEGY: &str = "gitrev";
+
+fn git(args: &[&str]) -> Result<Option<String>, Error> {
+    let out = Command::new("git").args(args).output()?;
+    if !out.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&out.stdout).trim().to_owned()))
+}
+
+/// The commit count since the last tag and the abbreviated hash, parsed from
+/// `git describe --tags --long --dirty` output of the form
+/// `<tag>-<count>-g<hash>[-dirty]`.
+struct Describe {
+    count: String,
+    hash: String,
+    dirty: bool,
+}
+
+fn parse_describe(raw: &str) -> Option<Describe> {
+    let (raw, dirty) = match raw.strip_suffix("-dirty") {
+        Some(rest) => (rest, true),
+        None => (raw, false),
+    };
+    // `<tag>-<count>-g<hash>`: peel the trailing two fields off the right so a
+    // tag containing dashes stays intact.
+    let (rest, hash) = raw.rsplit_once('-')?;
+    let (_tag, count) = rest.rsplit_once('-')?;
+    let hash = hash.strip_prefix('g')?;
+    Some(Describe {
+        count: count.to_owned(),
+        hash: hash.to_owned(),
+        dirty,
+    })
+}
+
+/// Compute a development version such as `1.4.2-dev.12+g3f8a1c2`, adding a
+/// `.dirty` build marker when the working tree has uncommitted changes.
+///
+/// Falls back to the short commit hash and commit count since the root when no
+/// tag is reachable, still building on the version recorded in `.vers`.
+pub fn compute(conf: &Config) -> Result<Version, Error> {
+    let base = conf.require_version()?;
+    let mut version: Version = base
+        .parse()
+        .map_err(|e| Error::Command(format!("invalid base version {base:?}: {e}")))?;
+
+    let (count, hash, dirty) = match git(&["describe", "--tags", "--long", "--dirty"])?
+        .as_deref()
+        .and_then(parse_describe)
+    {
+        Some(d) => (d.count, d.hash, d.dirty),
+        None => {
+            // No reachable tag: count every commit and take the short hash.
+            let count = git(&["rev-list", "--count", "HEAD"])?
+                .ok_or_else(|| Error::Command("git rev-list failed".to_owned()))?;
+            let hash = git(&["rev-parse", "--short", "HEAD"])?
+                .ok_or_else(|| Error::Command("git rev-parse failed".to_owned()))?;
+            let dirty = git(&["status", "--porcelain"])?
+                .map(|s| !s.is_empty())
+                .unwrap_or(false);
+            (count, hash, dirty)
+        }
+    };
+
+    version
+        .set_pre(&format!("dev.{count}"))
+        .map_err(|e| Error::Command(e.to_string()))?;
+    let build = if dirty {
+        format!("g{hash}.dirty")
+    } else {
+        format!("g{hash}")
+    };
+    version
+        .set_build(&build)
+        .map_err(|e| Error::Command(e.to_string()))?;
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_standard_describe_output() {
+        let d = parse_describe("v1.4.2-12-g3f8a1c2").unwrap();
+        assert_eq!(d.count, "12");
+        assert_eq!(d.hash, "3f8a1c2");
+        assert!(!d.dirty);
+    }
+
+    #[test]
+    fn parses_dirty_marker() {
+        let d = parse_describe("v1.4.2-12-g3f8a1c2-dirty").unwrap();
+        assert_eq!(d.count, "12");
+        assert_eq!(d.hash, "3f8a1c2");
+        assert!(d.dirty);
+    }
+
+    #[test]
+    fn keeps_tag_with_dashes_intact() {
+        // Only the trailing `-<count>-g<hash>` fields are peeled off the right.
+        let d = parse_describe("core-v1.0.0-3-gabc1234").unwrap();
+        assert_eq!(d.count, "3");
+        assert_eq!(d.hash, "abc1234");
+        assert!(!d.dirty);
+    }
+
+    #[test]
+    fn rejects_output_without_hash_field() {
+        assert!(parse_describe("v1.0.0").is_none());
+        assert!(parse_describe("v1.0.0-3-abc1234").is_none());
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// The crate-wide error type. Every fallible operation folds into one of these
+/// classes so callers — and the `main` dispatcher — see typed failures rather
+/// than panics.
+#[derive(Debug)]
+pub enum Error {
+    Git2(git2::Error),
+    Io(std::io::Error),
+    TomlDeserialize(toml::de::Error),
+    TomlSerialize(toml::ser::Error),
+    /// A required external command (e.g. `git log`) failed or was unusable, or
+    /// another failure described only by a message (e.g. a `.vers` without a
+    /// top-level `version`).
+    Command(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Git2(e) => write!(f, "git: {e}"),
+            Error::Io(e) => write!(f, "io: {e}"),
+            Error::TomlDeserialize(e) => write!(f, "toml: {e}"),
+            Error::TomlSerialize(e) => write!(f, "toml: {e}"),
+            Error::Command(e) => write!(f, "command: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<git2::Error> for Error {
+    fn from(e: git2::Error) -> Self {
+        Error::Git2(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::TomlDeserialize(e)
+    }
+}
+
+impl From<toml::ser::Error> for Error {
+    fn from(e: toml::ser::Error) -> Self {
+        Error::TomlSerialize(e)
+    }
+}
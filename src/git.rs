@@ -0,0 +1,78 @@
+use crate::error::Error;
+use git2::{Cred, PushOptions, RemoteCallbacks, Repository, Signature};
+
+/// A thin wrapper over a `git2::Repository` exposing the handful of operations
+/// the tool needs: staging, committing, tagging, and pushing.
+pub struct Backend {
+    repo: Repository,
+}
+
+impl Backend {
+    /// Open the repository containing the current directory.
+    pub fn open() -> Result<Self, Error> {
+        let repo = Repository::discover(".")?;
+        Ok(Self { repo })
+    }
+
+    fn signature(&self) -> Result<Signature<'static>, Error> {
+        Ok(self.repo.signature()?)
+    }
+
+    /// Stage every change in the working tree (tracked and untracked).
+    pub fn stage_all(&self) -> Result<(), Error> {
+        let mut index = self.repo.index()?;
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    /// Create a commit on HEAD from the current index.
+    pub fn commit(&self, message: &str) -> Result<git2::Oid, Error> {
+        let sig = self.signature()?;
+        let mut index = self.repo.index()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+
+        let parents = match self.repo.head() {
+            Ok(head) => vec![head.peel_to_commit()?],
+            // No HEAD yet: this is the initial commit.
+            Err(_) => Vec::new(),
+        };
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        let oid = self
+            .repo
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)?;
+        Ok(oid)
+    }
+
+    /// Create an annotated tag at HEAD named `name`.
+    pub fn tag(&self, name: &str, message: &str) -> Result<git2::Oid, Error> {
+        let sig = self.signature()?;
+        let target = self.repo.head()?.peel(git2::ObjectType::Commit)?;
+        let oid = self.repo.tag(name, &target, &sig, message, false)?;
+        Ok(oid)
+    }
+
+    /// Push the given refspecs to `remote`, authenticating via the ssh agent or
+    /// the git credential helper.
+    pub fn push(&self, remote: &str, refspecs: &[&str]) -> Result<(), Error> {
+        let mut remote = self.repo.find_remote(remote)?;
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(|url, username, allowed| {
+            if allowed.contains(git2::CredentialType::SSH_KEY) {
+                return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                if let Ok(cfg) = git2::Config::open_default() {
+                    return Cred::credential_helper(&cfg, url, username);
+                }
+            }
+            Cred::default()
+        });
+
+        let mut opts = PushOptions::new();
+        opts.remote_callbacks(callbacks);
+        remote.push(refspecs, Some(&mut opts))?;
+        Ok(())
+    }
+}
@@ -1,64 +1,15 @@
-use clap::Parser;
-use serde::Deserialize;
-use std::fmt::DebugStruct;
-use std::io;
-use std::io::Read;
-use std::io::Write;
-
-#[derive(Deserialize, Debug, Clone)]
-struct Config {
-    suffix: String,
-    version: String,
-}
-
-impl Config {
-    pub fn parse() -> Self {
-        let mut file = std::fs::File::open(".vers").unwrap();
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).unwrap();
-        let mut iter = contents.split('\n');
-        let version = iter.find(|s| s.starts_with("version"));
-        let suffix = iter.find(|s| s.starts_with("suffix"));
-        match (suffix, version) {
-            (Some(s), Some(v)) => Self {
-                suffix: s.replace("suffix = ", "").to_owned(),
-                version: v.replace("version = ", "").to_owned(),
-            },
-            _ => Self {
-                suffix: Suffix::Dev.to_string(),
-                version: "1.0.0".to_owned(),
-            },
-        }
-    }
-}
+mod changelog;
+mod config;
+mod describe;
+mod error;
+mod git;
+mod packages;
+mod version;
 
-impl std::fmt::Display for Config {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("suffix = {}\n", self.suffix))
-    }
-}
-
-#[derive(Debug, Default)]
-enum Suffix {
-    #[default]
-    Dev,
-    Test,
-    Rel,
-    Alpha,
-    Beta,
-}
-
-impl std::fmt::Display for Suffix {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Suffix::Dev => f.write_str("dev"),
-            Suffix::Test => f.write_str("test"),
-            Suffix::Rel => f.write_str("rel"),
-            Suffix::Alpha => f.write_str("alpha"),
-            Suffix::Beta => f.write_str("beta"),
-        }
-    }
-}
+use clap::Parser;
+use config::Config;
+use std::path::{Path, PathBuf};
+use version::Version;
 
 #[derive(clap::Parser)]
 struct App {
@@ -69,7 +20,7 @@ struct App {
 #[derive(clap::Subcommand)]
 enum Cmd {
     #[clap(name = "update")]
-    Update,
+    Update(UpdateOpts),
 
     #[clap(name = "commit")]
     Commit(CommitOpts),
@@ -79,145 +30,260 @@ enum Cmd {
 
     #[clap(name = "tags")]
     Tags,
+
+    #[clap(name = "changelog")]
+    Changelog(ChangelogOpts),
+
+    #[clap(name = "describe")]
+    Describe,
 }
 
 #[derive(clap::Args)]
-struct CommitOpts {
-    #[clap(long, short = 'm')]
-    message: String,
+struct ChangelogOpts {
+    /// Lower bound of the range (default: the last version tag).
+    #[clap(long)]
+    from: Option<String>,
+
+    /// Upper bound of the range (default: HEAD).
+    #[clap(long)]
+    to: Option<String>,
 
-    #[clap(long, short = 'o')]
-    other: Option<String>,
+    /// Version heading to render (default: the version from `.vers`).
+    #[clap(long)]
+    version: Option<String>,
+
+    /// File to prepend the rendered section to.
+    #[clap(long, short = 'o', default_value = "CHANGELOG.md")]
+    output: String,
 }
 
 #[derive(clap::Args)]
-struct PushOpts {
-    #[clap(long, short = 'o')]
-    other: Option<String>,
+#[clap(group = clap::ArgGroup::new("level").multiple(false))]
+struct UpdateOpts {
+    #[clap(long, group = "level")]
+    major: bool,
+
+    #[clap(long, group = "level")]
+    minor: bool,
+
+    #[clap(long, group = "level")]
+    patch: bool,
+
+    #[clap(long)]
+    pre: Option<String>,
+
+    #[clap(long)]
+    set: Option<String>,
+
+    /// Regenerate CHANGELOG.md for the bumped version as part of the update.
+    #[clap(long)]
+    changelog: bool,
+
+    /// In a monorepo, restrict the bump to the named package(s).
+    #[clap(long = "package", short = 'p')]
+    packages: Vec<String>,
+
+    /// In a monorepo, only bump packages whose files changed over this range
+    /// (e.g. `v1.0.0..HEAD`).
+    #[clap(long)]
+    changed: Option<String>,
 }
 
-#[derive(Debug)]
-struct Version {
-    major: u32,
-    minor: u32,
-    patch: u32,
+#[derive(clap::Args)]
+struct CommitOpts {
+    #[clap(long, short = 'm')]
+    message: String,
 }
 
-impl Version {
-    fn incr(&mut self) {
-        self.patch += 1;
-        if self.patch > 9 {
-            self.patch = 0;
-            self.minor += 1;
-        }
-        if self.minor > 9 {
-            self.minor = 0;
-            self.major += 1;
+#[derive(clap::Args)]
+struct PushOpts {}
+
+fn parse_or_exit(s: &str) -> Version {
+    match s.parse::<Version>() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("error: could not parse version {s:?}: {e}");
+            std::process::exit(1);
         }
     }
 }
 
-impl std::fmt::Display for Version {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!(
-            "version = {}.{}.{}",
-            self.major, self.minor, self.patch
-        ))
+fn run_commit(message: &str) -> Result<(), error::Error> {
+    let backend = git::Backend::open()?;
+    backend.stage_all()?;
+    let oid = backend.commit(message)?;
+    println!("committed {oid}");
+    Ok(())
+}
+
+fn run_push(conf: &Config) -> Result<(), error::Error> {
+    let backend = git::Backend::open()?;
+    let refspec = format!("refs/heads/{0}:refs/heads/{0}", conf.branch);
+    backend.push(&conf.remote, &[&refspec])?;
+    println!("pushed {} to {}", conf.branch, conf.remote);
+    Ok(())
+}
+
+fn run_tags(conf: &Config) -> Result<(), error::Error> {
+    let backend = git::Backend::open()?;
+    // In a monorepo, tag each package at its own version; otherwise a single
+    // tag from the top-level template.
+    let names: Vec<String> = if conf.packages.is_empty() {
+        vec![conf.tag_name(conf.require_version()?)]
+    } else {
+        conf.packages.iter().map(|p| p.tag_name()).collect()
+    };
+    for name in names {
+        backend.tag(&name, &format!("Release {name}"))?;
+        backend.push(&conf.remote, &[&format!("refs/tags/{name}")])?;
+        println!("created and pushed tag {name}");
     }
+    Ok(())
 }
 
-impl From<&str> for Version {
-    fn from(s: &str) -> Self {
-        let mut iter = s.split('.');
-        let major = iter.next().unwrap().parse().unwrap();
-        let minor = iter.next().unwrap().parse().unwrap();
-        let patch = iter.next().unwrap().parse().unwrap();
-        Self {
-            major,
-            minor,
-            patch,
+/// Apply the bump/pre/set selection from `op` to `current` and return the new
+/// version.
+fn apply_bump(op: &UpdateOpts, current: &str, default: config::BumpLevel) -> Version {
+    let mut v = match op.set {
+        Some(ref s) => parse_or_exit(s.trim()),
+        None => parse_or_exit(current.trim()),
+    };
+    // --set pins an explicit version, so it skips the bump entirely.
+    if op.set.is_none() {
+        let level = if op.major {
+            config::BumpLevel::Major
+        } else if op.minor {
+            config::BumpLevel::Minor
+        } else if op.patch {
+            config::BumpLevel::Patch
+        } else {
+            default
+        };
+        match level {
+            config::BumpLevel::Major => v.bump_major(),
+            config::BumpLevel::Minor => v.bump_minor(),
+            config::BumpLevel::Patch => v.bump_patch(),
         }
     }
+    if let Some(ref pre) = op.pre {
+        if let Err(e) = v.set_pre(pre.trim()) {
+            eprintln!("error: invalid --pre identifier {pre:?}: {e}");
+            std::process::exit(1);
+        }
+    }
+    v
 }
 
-struct Rw(Config);
+fn run_update(conf: &mut Config, path: &Path, op: &UpdateOpts) -> Result<(), error::Error> {
+    if conf.packages.is_empty() {
+        // When the gitrev strategy is selected, derive the version from git
+        // metadata and print it without persisting a bump.
+        if conf.suffix.as_deref() == Some(describe::STRATEGY) {
+            println!("{}", describe::compute(conf)?);
+            return Ok(());
+        }
+        let v = apply_bump(op, conf.require_version()?, conf.bump);
+        let rendered = v.to_string();
+        conf.version = Some(rendered.clone());
+        conf.save(path)?;
+        println!("{rendered}");
+        if op.changelog {
+            changelog::generate(&rendered, None, None, "CHANGELOG.md")?;
+        }
+        return Ok(());
+    }
 
-impl Rw {
-    fn write(&self, version: Version, path: &str) {
-        let mut file = std::fs::File::create(path).unwrap();
-        let out = version.to_string() + "\n" + &self.0.to_string();
-        file.write_all(out.as_bytes()).unwrap();
+    // Per-package changelogs aren't modeled yet, so reject the combination
+    // rather than silently dropping the flag.
+    if op.changelog {
+        return Err(error::Error::Command(
+            "--changelog is not supported in monorepo mode".to_owned(),
+        ));
     }
-}
 
-fn commit(msg: &str, others: Vec<&str>) {
-    let _others = match others.len() {
-        0 => vec!["-a"],
-        _ => others,
-    };
-    let cmd = std::process::Command::new("git")
-        .arg("commit")
-        .arg("-m")
-        .arg(msg)
-        .args(_others)
-        .output()
-        .unwrap();
-    println!("status: {}", cmd.status);
-    io::stdout().write_all(&cmd.stdout).unwrap();
-    io::stderr().write_all(&cmd.stderr).unwrap();
-}
+    // Monorepo: start from the selected subset (all by default), then optionally
+    // narrow to packages whose files changed over the given range.
+    let mut selected: Vec<usize> = (0..conf.packages.len())
+        .filter(|&i| op.packages.is_empty() || op.packages.contains(&conf.packages[i].name))
+        .collect();
+    if let Some(range) = &op.changed {
+        let (from, to) = range.split_once("..").unwrap_or((range.as_str(), "HEAD"));
+        let changed = packages::changed_packages(&conf.packages, from, to)?;
+        selected.retain(|i| changed.contains(i));
+    }
 
-fn push(others: Vec<&str>) {
-    let _others = match others.len() {
-        0 => vec!["origin", "main"],
-        _ => others,
-    };
-    let cmd = std::process::Command::new("git")
-        .arg("push")
-        .args(_others)
-        .output()
-        .unwrap();
-    println!("status: {}", cmd.status);
-    io::stdout().write_all(&cmd.stdout).unwrap();
-    io::stderr().write_all(&cmd.stderr).unwrap();
+    let default = conf.bump;
+    for i in selected {
+        let v = apply_bump(op, &conf.packages[i].version, default);
+        conf.packages[i].version = v.to_string();
+        println!("{} -> {}", conf.packages[i].name, conf.packages[i].version);
+    }
+    conf.save(path)?;
+    Ok(())
 }
 
 fn main() {
-    let conf = Config::parse();
-    let rw = Rw(conf.clone());
+    let (mut conf, path): (Config, PathBuf) = match Config::discover() {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    };
     let app = App::parse();
 
     match app.cmd {
-        Cmd::Update => {
-            let mut v = Version::from(conf.version.as_str());
-            v.incr();
-            rw.write(v, ".vers");
+        Cmd::Update(op) => {
+            if let Err(e) = run_update(&mut conf, &path, &op) {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
         }
         Cmd::Commit(op) => {
-            let others = match op.other {
-                Some(s) => s,
-                None => "".to_owned(),
-            };
-            let other_args = others.split(' ').collect::<Vec<&str>>();
-            commit(&op.message, other_args);
+            if let Err(e) = run_commit(&op.message) {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
         }
-        Cmd::Push(op) => {
-            let others = match op.other {
-                Some(s) => s,
-                None => "".to_owned(),
-            };
-            let other_args = others.split(' ').collect::<Vec<&str>>();
-            push(other_args);
+        Cmd::Push(_) => {
+            if let Err(e) = run_push(&conf) {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
         }
         Cmd::Tags => {
-            let cmd = std::process::Command::new("git")
-                .arg("push")
-                .arg("--tag")
-                .output()
-                .unwrap();
-            println!("status: {}", cmd.status);
-            io::stdout().write_all(&cmd.stdout).unwrap();
-            io::stderr().write_all(&cmd.stderr).unwrap();
+            if let Err(e) = run_tags(&conf) {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Cmd::Changelog(op) => {
+            let version = match op.version {
+                Some(v) => v,
+                None => match conf.require_version() {
+                    Ok(v) => v.to_owned(),
+                    Err(e) => {
+                        eprintln!("error: {e}");
+                        std::process::exit(1);
+                    }
+                },
+            };
+            if let Err(e) = changelog::generate(
+                &version,
+                op.from.as_deref(),
+                op.to.as_deref(),
+                &op.output,
+            ) {
+                eprintln!("error: could not generate changelog: {e}");
+                std::process::exit(1);
+            }
         }
+        Cmd::Describe => match describe::compute(&conf) {
+            Ok(v) => println!("{v}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        },
     }
 }
@@ -0,0 +1,107 @@
+use crate::config::Package;
+use crate::error::Error;
+use std::process::Command;
+
+/// Find the package that owns `path` by longest-prefix match.
+///
+/// Packages are matched on a normalized, `/`-terminated path so that a package
+/// rooted at `crates/core` claims `crates/core/src/lib.rs` but not
+/// `crates/core-utils/...`.
+pub fn owning_package<'a>(packages: &'a [Package], path: &str) -> Option<&'a Package> {
+    // Longest-prefix wins: visit candidates from the deepest root upward.
+    let mut sorted: Vec<&Package> = packages.iter().collect();
+    sorted.sort_by_key(|p| std::cmp::Reverse(p.path.len()));
+    sorted.into_iter().find(|p| path_in_root(path, &p.path))
+}
+
+fn path_in_root(path: &str, root: &str) -> bool {
+    let root = root.trim_end_matches('/');
+    if root.is_empty() || root == "." {
+        return true;
+    }
+    path == root || path.strip_prefix(root).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The set of repo-relative paths that changed over `<from>..<to>`.
+fn changed_paths(from: &str, to: &str) -> Result<Vec<String>, Error> {
+    let range = format!("{from}..{to}");
+    let out = Command::new("git")
+        .args(["diff", "--name-only", &range])
+        .output()?;
+    if !out.status.success() {
+        return Err(Error::Command(
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|l| l.trim().to_owned())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+/// Indices of the packages with at least one file changed over `<from>..<to>`,
+/// mapping each changed path to its owner by longest-prefix match.
+pub fn changed_packages(
+    packages: &[Package],
+    from: &str,
+    to: &str,
+) -> Result<Vec<usize>, Error> {
+    let mut touched = vec![false; packages.len()];
+    for path in changed_paths(from, to)? {
+        if let Some(owner) = owning_package(packages, &path) {
+            // `owning_package` returns a borrow into `packages`; recover its index.
+            if let Some(idx) = packages.iter().position(|p| p.name == owner.name) {
+                touched[idx] = true;
+            }
+        }
+    }
+    Ok(touched
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, t)| t.then_some(i))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, path: &str) -> Package {
+        Package {
+            name: name.to_owned(),
+            path: path.to_owned(),
+            version: "0.1.0".to_owned(),
+            suffix: None,
+            tag_prefix: String::new(),
+        }
+    }
+
+    #[test]
+    fn matches_owner_by_root() {
+        let packages = [pkg("core", "crates/core"), pkg("cli", "crates/cli")];
+        let owner = owning_package(&packages, "crates/core/src/lib.rs").unwrap();
+        assert_eq!(owner.name, "core");
+    }
+
+    #[test]
+    fn prefix_does_not_leak_across_sibling_with_shared_prefix() {
+        // `core` must not claim files under `core-utils`.
+        let packages = [pkg("core", "crates/core"), pkg("core-utils", "crates/core-utils")];
+        let owner = owning_package(&packages, "crates/core-utils/src/lib.rs").unwrap();
+        assert_eq!(owner.name, "core-utils");
+    }
+
+    #[test]
+    fn longest_prefix_wins_for_nested_roots() {
+        let packages = [pkg("outer", "crates"), pkg("inner", "crates/inner")];
+        let owner = owning_package(&packages, "crates/inner/src/lib.rs").unwrap();
+        assert_eq!(owner.name, "inner");
+    }
+
+    #[test]
+    fn unowned_path_has_no_package() {
+        let packages = [pkg("core", "crates/core")];
+        assert!(owning_package(&packages, "docs/readme.md").is_none());
+    }
+}
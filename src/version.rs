@@ -0,0 +1,405 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single pre-release identifier. Per SemVer 2.0.0 an identifier that is made
+/// up solely of digits is compared numerically, everything else lexically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Identifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+impl Identifier {
+    fn parse(s: &str) -> Result<Self, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::EmptyIdentifier);
+        }
+        // A numeric identifier must not have leading zeroes.
+        if s.bytes().all(|b| b.is_ascii_digit()) {
+            if s.len() > 1 && s.starts_with('0') {
+                return Err(ParseError::LeadingZero(s.to_owned()));
+            }
+            Ok(Identifier::Numeric(s.parse().map_err(|_| {
+                ParseError::InvalidIdentifier(s.to_owned())
+            })?))
+        } else if s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            Ok(Identifier::Alphanumeric(s.to_owned()))
+        } else {
+            Err(ParseError::InvalidIdentifier(s.to_owned()))
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{n}"),
+            Identifier::Alphanumeric(s) => f.write_str(s),
+        }
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Alphanumeric(a), Identifier::Alphanumeric(b)) => a.cmp(b),
+            // Numeric identifiers always have lower precedence than alphanumeric ones.
+            (Identifier::Numeric(_), Identifier::Alphanumeric(_)) => Ordering::Less,
+            (Identifier::Alphanumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Errors produced while parsing a [`Version`] from its textual form.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The core did not have exactly the three `MAJOR.MINOR.PATCH` fields.
+    MissingField,
+    /// A numeric core field could not be parsed as an integer.
+    InvalidNumber(String),
+    /// A pre-release/build segment contained an empty identifier (e.g. `1.0.0-`).
+    EmptyIdentifier,
+    /// A numeric identifier carried a disallowed leading zero.
+    LeadingZero(String),
+    /// A pre-release or build identifier contained characters outside
+    /// `[0-9A-Za-z-]`, or a numeric pre-release identifier did not fit in a `u64`.
+    InvalidIdentifier(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingField => f.write_str("expected MAJOR.MINOR.PATCH"),
+            ParseError::InvalidNumber(s) => write!(f, "invalid version number: {s}"),
+            ParseError::EmptyIdentifier => f.write_str("empty pre-release/build identifier"),
+            ParseError::LeadingZero(s) => write!(f, "numeric identifier has leading zero: {s}"),
+            ParseError::InvalidIdentifier(s) => write!(f, "invalid identifier: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A SemVer 2.0.0 version: `MAJOR.MINOR.PATCH[-prerelease][+build]`.
+///
+/// Build metadata is retained for round-tripping but, per the spec, is ignored
+/// when comparing versions for precedence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pre: Vec<Identifier>,
+    build: Vec<String>,
+}
+
+impl Version {
+    /// Construct a bare `MAJOR.MINOR.PATCH` version with no pre-release or build.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    /// Increment the patch level, clearing any pre-release identifiers.
+    pub fn incr(&mut self) {
+        self.bump_patch();
+    }
+
+    /// Bump the major level, resetting minor and patch to 0.
+    pub fn bump_major(&mut self) {
+        self.major += 1;
+        self.minor = 0;
+        self.patch = 0;
+        self.pre.clear();
+    }
+
+    /// Bump the minor level, resetting patch to 0.
+    pub fn bump_minor(&mut self) {
+        self.minor += 1;
+        self.patch = 0;
+        self.pre.clear();
+    }
+
+    /// Bump the patch level.
+    pub fn bump_patch(&mut self) {
+        self.patch += 1;
+        self.pre.clear();
+    }
+
+    /// Whether this version carries a pre-release segment.
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Replace the pre-release segment, parsing `ident` as a dot-separated list.
+    /// An empty string clears the pre-release.
+    pub fn set_pre(&mut self, ident: &str) -> Result<(), ParseError> {
+        self.pre = parse_identifiers(ident)?;
+        Ok(())
+    }
+
+    /// Replace the build metadata segment, parsing `ident` as a dot-separated
+    /// list. Build metadata is never compared for precedence. An empty string
+    /// clears it.
+    pub fn set_build(&mut self, ident: &str) -> Result<(), ParseError> {
+        if ident.is_empty() {
+            self.build.clear();
+            return Ok(());
+        }
+        self.build = parse_build(ident)?;
+        Ok(())
+    }
+
+    fn parse_core(s: &str) -> Result<u64, ParseError> {
+        if s.len() > 1 && s.starts_with('0') {
+            return Err(ParseError::LeadingZero(s.to_owned()));
+        }
+        s.parse().map_err(|_| ParseError::InvalidNumber(s.to_owned()))
+    }
+}
+
+fn parse_identifiers(s: &str) -> Result<Vec<Identifier>, ParseError> {
+    if s.is_empty() {
+        return Ok(Vec::new());
+    }
+    s.split('.').map(Identifier::parse).collect()
+}
+
+/// Parse a dot-separated build-metadata segment. Build identifiers are never
+/// compared for precedence, but per the spec they must still be non-empty and
+/// drawn from `[0-9A-Za-z-]`.
+fn parse_build(s: &str) -> Result<Vec<String>, ParseError> {
+    s.split('.')
+        .map(|b| {
+            if b.is_empty() {
+                Err(ParseError::EmptyIdentifier)
+            } else if b.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-') {
+                Ok(b.to_owned())
+            } else {
+                Err(ParseError::InvalidIdentifier(b.to_owned()))
+            }
+        })
+        .collect()
+}
+
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, build) = match s.split_once('+') {
+            Some((rest, build)) => (rest, parse_build(build)?),
+            None => (s, Vec::new()),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            // A present-but-empty pre-release (e.g. `1.0.0-`) is malformed, not
+            // an absent one.
+            Some((_, "")) => return Err(ParseError::EmptyIdentifier),
+            Some((core, pre)) => (core, parse_identifiers(pre)?),
+            None => (rest, Vec::new()),
+        };
+
+        let mut fields = core.split('.');
+        let major = Version::parse_core(fields.next().ok_or(ParseError::MissingField)?)?;
+        let minor = Version::parse_core(fields.next().ok_or(ParseError::MissingField)?)?;
+        let patch = Version::parse_core(fields.next().ok_or(ParseError::MissingField)?)?;
+        if fields.next().is_some() {
+            return Err(ParseError::MissingField);
+        }
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            f.write_str("-")?;
+            for (i, id) in self.pre.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(".")?;
+                }
+                write!(f, "{id}")?;
+            }
+        }
+        if !self.build.is_empty() {
+            f.write_str("+")?;
+            for (i, b) in self.build.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(".")?;
+                }
+                f.write_str(b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.cmp(&other.minor))
+            .then_with(|| self.patch.cmp(&other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A version with a pre-release is lower than one without.
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    for (a, b) in self.pre.iter().zip(other.pre.iter()) {
+                        let ord = a.cmp(b);
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                    // All shared identifiers equal: the longer set wins.
+                    self.pre.len().cmp(&other.pre.len())
+                }
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_core_pre_and_build() {
+        let v = parse("1.4.2-beta.3+build.7");
+        assert_eq!((v.major, v.minor, v.patch), (1, 4, 2));
+        assert_eq!(v.to_string(), "1.4.2-beta.3+build.7");
+        assert!(v.is_prerelease());
+    }
+
+    #[test]
+    fn round_trips_without_segments() {
+        assert_eq!(parse("0.1.0").to_string(), "0.1.0");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!("1.0".parse::<Version>(), Err(ParseError::MissingField));
+        assert_eq!("1.2.3.4".parse::<Version>(), Err(ParseError::MissingField));
+        assert_eq!("1.x.0".parse::<Version>(), Err(ParseError::InvalidNumber("x".into())));
+        assert_eq!("01.0.0".parse::<Version>(), Err(ParseError::LeadingZero("01".into())));
+    }
+
+    #[test]
+    fn rejects_empty_prerelease_and_build() {
+        // A present-but-empty segment is malformed for both pre-release and build.
+        assert_eq!("1.0.0-".parse::<Version>(), Err(ParseError::EmptyIdentifier));
+        assert_eq!("1.0.0+".parse::<Version>(), Err(ParseError::EmptyIdentifier));
+        assert_eq!("1.0.0-a..b".parse::<Version>(), Err(ParseError::EmptyIdentifier));
+    }
+
+    #[test]
+    fn rejects_leading_zero_numeric_identifier() {
+        assert_eq!("1.0.0-01".parse::<Version>(), Err(ParseError::LeadingZero("01".into())));
+    }
+
+    #[test]
+    fn rejects_out_of_charset_build_identifier() {
+        assert_eq!(
+            "1.0.0+bad_id!".parse::<Version>(),
+            Err(ParseError::InvalidIdentifier("bad_id!".into()))
+        );
+    }
+
+    #[test]
+    fn prerelease_is_lower_than_release() {
+        assert!(parse("1.0.0-alpha") < parse("1.0.0"));
+    }
+
+    #[test]
+    fn build_metadata_is_ignored_in_precedence() {
+        assert_eq!(parse("1.0.0+a").cmp(&parse("1.0.0+b")), Ordering::Equal);
+    }
+
+    #[test]
+    fn prerelease_precedence_follows_spec() {
+        // The canonical ordering example from the SemVer 2.0.0 spec.
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn numeric_identifiers_order_below_alphanumeric() {
+        assert!(parse("1.0.0-1") < parse("1.0.0-alpha"));
+    }
+
+    #[test]
+    fn longer_identifier_set_wins_when_prefix_equal() {
+        assert!(parse("1.0.0-alpha") < parse("1.0.0-alpha.1"));
+    }
+
+    #[test]
+    fn patch_bump_clears_prerelease_without_ceiling() {
+        let mut v = parse("1.2.9-beta.1");
+        v.bump_patch();
+        assert_eq!(v.to_string(), "1.2.10");
+    }
+
+    #[test]
+    fn minor_bump_resets_patch() {
+        let mut v = parse("1.2.9");
+        v.bump_minor();
+        assert_eq!(v.to_string(), "1.3.0");
+    }
+
+    #[test]
+    fn major_bump_resets_minor_and_patch() {
+        let mut v = parse("1.2.9");
+        v.bump_major();
+        assert_eq!(v.to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn set_pre_then_clear() {
+        let mut v = Version::new(1, 0, 0);
+        v.set_pre("rc.1").unwrap();
+        assert_eq!(v.to_string(), "1.0.0-rc.1");
+        v.set_pre("").unwrap();
+        assert_eq!(v.to_string(), "1.0.0");
+    }
+}